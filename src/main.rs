@@ -2,12 +2,61 @@ use web_sys::{HtmlInputElement, Url, Element, HtmlSelectElement};
 use yew::prelude::*;
 use gloo_file::File;
 use gloo_file::futures::read_as_bytes;
+use gloo_worker::{Spawnable, WorkerBridge};
 use wasm_bindgen::JsCast;
 
+mod search_algo;
+mod search_worker;
+
+use search_worker::{SearchRequest, SearchResponse, SearchResult, SearchWorker};
+
 const BYTES_PER_ROW: usize = 16;
 const ROW_HEIGHT: f64 = 29.6;
 const OVERSCAN_ROWS: usize = 10;
 
+/// Parses a single hex nibble, where `?` is a wildcard. Returns `(mask, value)`
+/// with the wildcard contributing `0` to both.
+fn parse_nibble(c: char) -> Option<(u8, u8)> {
+    if c == '?' {
+        Some((0x0, 0x0))
+    } else {
+        c.to_digit(16).map(|d| (0xF, d as u8))
+    }
+}
+
+/// Parses a cleaned (whitespace-stripped) hex search term into a sequence of
+/// `(mask, value)` byte pairs, where `??`, `D?` and `?F`-style tokens act as
+/// full- or half-nibble wildcards. Odd-length input or non-hex/non-`?`
+/// characters are rejected.
+fn parse_hex_pattern(cleaned: &str) -> Result<Vec<(u8, u8)>, ()> {
+    let chars: Vec<char> = cleaned.chars().collect();
+    if chars.len() % 2 != 0 {
+        return Err(());
+    }
+    chars
+        .chunks(2)
+        .map(|pair| {
+            let (hi_mask, hi_val) = parse_nibble(pair[0]).ok_or(())?;
+            let (lo_mask, lo_val) = parse_nibble(pair[1]).ok_or(())?;
+            Ok(((hi_mask << 4) | lo_mask, (hi_val << 4) | lo_val))
+        })
+        .collect()
+}
+
+/// Parses a decimal or `0x`-prefixed hex offset typed into the search range
+/// inputs. Blank or unparsable text means "no bound".
+fn parse_offset(text: &str) -> Option<usize> {
+    let trimmed = text.trim();
+    if trimmed.is_empty() {
+        return None;
+    }
+    if let Some(hex) = trimmed.strip_prefix("0x").or_else(|| trimmed.strip_prefix("0X")) {
+        usize::from_str_radix(hex, 16).ok()
+    } else {
+        trimmed.parse().ok()
+    }
+}
+
 #[derive(Clone, Copy, PartialEq, Debug)]
 pub enum SearchMode {
     Hex,
@@ -23,7 +72,13 @@ pub enum Msg {
     Scrolled(Event),
     UpdateSearchTerm(String),
     UpdateSearchMode(SearchMode),
+    UpdateSearchStart(String),
+    UpdateSearchEnd(String),
+    ToggleSearchInvert,
     ExecuteSearch,
+    SearchResultsReady(u64, Vec<SearchResult>),
+    SearchComplete(u64),
+    SelectResult(usize),
     FindNext,
     FindPrevious,
 }
@@ -37,17 +92,40 @@ pub struct HexEditor {
     scroll_container_ref: NodeRef,
     search_term: String,
     search_mode: SearchMode,
-    search_bytes: Vec<u8>,
-    search_results: Vec<usize>,
+    search_start_text: String,
+    search_end_text: String,
+    search_invert: bool,
+    search_span_desc: String,
+    search_results: Vec<SearchResult>,
     current_match_index: Option<usize>,
     search_status: String,
+    is_searching: bool,
+    /// Monotonically increasing id for the search currently in flight, so
+    /// responses from a superseded search (which may still be scanning on
+    /// the shared worker when a newer one is dispatched) can be told apart
+    /// from the latest one and dropped.
+    search_generation: u64,
+    search_bridge: WorkerBridge<SearchWorker>,
 }
 
 impl Component for HexEditor {
     type Message = Msg;
     type Properties = ();
 
-    fn create(_ctx: &Context<Self>) -> Self {
+    fn create(ctx: &Context<Self>) -> Self {
+        // One dedicated worker, spawned once and reused for every search;
+        // re-instantiating the wasm module per search would defeat the point
+        // of moving the scan off the main thread.
+        let link = ctx.link().clone();
+        let search_bridge = SearchWorker::spawner()
+            .callback(move |response: SearchResponse| match response {
+                SearchResponse::Partial(generation, chunk) => {
+                    link.send_message(Msg::SearchResultsReady(generation, chunk))
+                }
+                SearchResponse::Done(generation) => link.send_message(Msg::SearchComplete(generation)),
+            })
+            .spawn("search_worker.js");
+
         Self {
             file_name: "no file loaded".to_string(),
             file_data: Vec::new(),
@@ -57,10 +135,16 @@ impl Component for HexEditor {
             scroll_container_ref: NodeRef::default(),
             search_term: String::new(),
             search_mode: SearchMode::Ascii,
-            search_bytes: Vec::new(),
+            search_start_text: String::new(),
+            search_end_text: String::new(),
+            search_invert: false,
+            search_span_desc: String::new(),
             search_results: Vec::new(),
             current_match_index: None,
             search_status: String::new(),
+            is_searching: false,
+            search_generation: 0,
+            search_bridge,
         }
     }
 
@@ -84,12 +168,18 @@ impl Component for HexEditor {
                 self.search_results.clear();
                 self.current_match_index = None;
                 self.search_status.clear();
+                self.is_searching = false;
+                // Invalidates any search still in flight against the old
+                // file's bytes, so its results can't land on the new file.
+                self.search_generation += 1;
                 true
             }
             Msg::FileLoadError(err_msg) => {
                 self.error = Some(format!("Error loading file: {}", err_msg));
                 self.file_data.clear();
                 self.file_name = "no file loaded".to_string();
+                self.is_searching = false;
+                self.search_generation += 1;
                 true
             }
             Msg::UpdateByte(index, hex_value) => {
@@ -127,41 +217,95 @@ impl Component for HexEditor {
                 self.search_mode = mode;
                 true
             }
+            Msg::UpdateSearchStart(text) => {
+                self.search_start_text = text;
+                true
+            }
+            Msg::UpdateSearchEnd(text) => {
+                self.search_end_text = text;
+                true
+            }
+            Msg::ToggleSearchInvert => {
+                self.search_invert = !self.search_invert;
+                true
+            }
             Msg::ExecuteSearch => {
                 self.search_results.clear();
                 self.current_match_index = None;
-                let search_bytes = match self.search_mode {
-                    SearchMode::Ascii => self.search_term.as_bytes().to_vec(),
+                let pattern = match self.search_mode {
+                    SearchMode::Ascii => self.search_term.as_bytes().iter().map(|&b| (0xFF, b)).collect(),
                     SearchMode::Hex => {
                         let cleaned: String = self.search_term.chars().filter(|c| !c.is_whitespace()).collect();
-                        match hex::decode(cleaned) {
-                            Ok(bytes) => bytes,
-                            Err(_) => {
+                        match parse_hex_pattern(&cleaned) {
+                            Ok(pattern) => pattern,
+                            Err(()) => {
                                 self.search_status = "Invalid Hex sequence.".to_string();
                                 return true;
                             }
                         }
                     }
                 };
-                if search_bytes.is_empty() {
+                if pattern.is_empty() {
                     self.search_status = "".to_string();
+                    self.is_searching = false;
                     return true;
                 }
-                self.search_bytes = search_bytes.clone();
-                self.search_results = self.file_data
-                    .windows(search_bytes.len())
-                    .enumerate()
-                    .filter_map(|(i, window)| if window == search_bytes.as_slice() { Some(i) } else { None })
-                    .collect();
-                if self.search_results.is_empty() {
-                    self.search_status = "Not found.".to_string();
+                self.is_searching = true;
+                self.search_generation += 1;
+
+                let start = parse_offset(&self.search_start_text)
+                    .unwrap_or(0)
+                    .min(self.file_data.len());
+                let end = parse_offset(&self.search_end_text)
+                    .unwrap_or(self.file_data.len())
+                    .min(self.file_data.len());
+                self.search_span_desc = if self.search_invert {
+                    format!("outside {:#010X}..{:#010X}", start, end)
                 } else {
-                    let count = self.search_results.len();
-                    self.search_status = format!("Found {} match(es).", count);
+                    format!("{:#010X}..{:#010X}", start, end)
+                };
+                self.search_status = format!("Found 0 so far (scanning {})...", self.search_span_desc);
+
+                self.search_bridge.send(SearchRequest {
+                    generation: self.search_generation,
+                    haystack: self.file_data.clone(),
+                    pattern,
+                    range_start: start,
+                    range_end: end,
+                    invert_range: self.search_invert,
+                });
+                true
+            }
+            Msg::SearchResultsReady(generation, chunk) => {
+                if generation != self.search_generation {
+                    // A superseded search's results; the worker is long-lived so
+                    // there's no bridge to drop to discard these automatically.
+                    return false;
+                }
+                let is_first_chunk = self.search_results.is_empty();
+                self.search_results.extend(chunk);
+                self.search_status = format!("Found {} so far (scanning {})...", self.search_results.len(), self.search_span_desc);
+                if is_first_chunk {
                     self.jump_to_match(0);
                 }
                 true
             }
+            Msg::SearchComplete(generation) => {
+                if generation != self.search_generation {
+                    return false;
+                }
+                self.is_searching = false;
+                self.search_status = if self.search_results.is_empty() {
+                    format!("Not found (scanned {}).", self.search_span_desc)
+                } else {
+                    format!("Found {} match(es) (scanned {}).", self.search_results.len(), self.search_span_desc)
+                };
+                true
+            }
+            Msg::SelectResult(index) => {
+                self.jump_to_match(index);
+                true
+            }
             Msg::FindNext => {
                 if !self.search_results.is_empty() {
                     let next_index = self.current_match_index.map_or(0, |i| (i + 1) % self.search_results.len());
@@ -211,7 +355,16 @@ impl Component for HexEditor {
                 _ => Msg::UpdateSearchMode(SearchMode::Ascii),
             }
         });
-        
+        let on_search_start_input = link.callback(|e: InputEvent| {
+            let input: HtmlInputElement = e.target_unchecked_into();
+            Msg::UpdateSearchStart(input.value())
+        });
+        let on_search_end_input = link.callback(|e: InputEvent| {
+            let input: HtmlInputElement = e.target_unchecked_into();
+            Msg::UpdateSearchEnd(input.value())
+        });
+        let on_search_invert_change = link.callback(|_: Event| Msg::ToggleSearchInvert);
+
         let total_rows = (self.file_data.len() as f64 / BYTES_PER_ROW as f64).ceil() as usize;
         let total_height = total_rows as f64 * ROW_HEIGHT;
         let first_visible_row = (self.scroll_top / ROW_HEIGHT).floor() as usize;
@@ -255,13 +408,43 @@ impl Component for HexEditor {
                                 <option value="hex" selected={self.search_mode == SearchMode::Hex}>{"Hex"}</option>
                             </select>
                             <input type="text" class="form-control" placeholder="Enter search term..." value={self.search_term.clone()} oninput={on_search_input} />
-                            <button class="btn btn-secondary" onclick={link.callback(|_| Msg::ExecuteSearch)}>{"Search"}</button>
+                            <button class="btn btn-secondary" onclick={link.callback(|_| Msg::ExecuteSearch)} disabled={self.is_searching}>{"Search"}</button>
                             <div class="btn-group">
                                 <button class="btn btn-outline-secondary" onclick={link.callback(|_| Msg::FindPrevious)} disabled={self.search_results.is_empty()}>{"<"}</button>
                                 <button class="btn btn-outline-secondary" onclick={link.callback(|_| Msg::FindNext)} disabled={self.search_results.is_empty()}>{">"}</button>
                             </div>
                         </div>
-                        <div class="form-text mt-1">{ &self.search_status }</div>
+                        <div class="d-flex gap-2 align-items-center mt-2">
+                            <span class="text-secondary small">{ "Range:" }</span>
+                            <input type="text" class="form-control form-control-sm" style="width: 140px;" placeholder="start (0x...)" value={self.search_start_text.clone()} oninput={on_search_start_input} />
+                            <span class="text-secondary">{ "-" }</span>
+                            <input type="text" class="form-control form-control-sm" style="width: 140px;" placeholder="end (0x...)" value={self.search_end_text.clone()} oninput={on_search_end_input} />
+                            <div class="form-check ms-2">
+                                <input class="form-check-input" type="checkbox" id="search-invert" checked={self.search_invert} onchange={on_search_invert_change} />
+                                <label class="form-check-label small" for="search-invert">{ "Exclude range" }</label>
+                            </div>
+                        </div>
+                        <div class="form-text mt-1 d-flex align-items-center gap-2">
+                            if self.is_searching {
+                                <span class="spinner-border spinner-border-sm text-secondary" role="status" aria-hidden="true"></span>
+                            }
+                            <span>{ &self.search_status }</span>
+                        </div>
+                        if !self.search_results.is_empty() {
+                            <ul class="list-group search-results-panel mt-2">
+                                { for self.search_results.iter().enumerate().map(|(idx, result)| {
+                                    let mut class = "list-group-item list-group-item-action py-1".to_string();
+                                    if self.current_match_index == Some(idx) {
+                                        class.push_str(" active");
+                                    }
+                                    html!{
+                                        <li {class} onclick={link.callback(move |_| Msg::SelectResult(idx))}>
+                                            { format!("{:#010X}", result.offset) }
+                                        </li>
+                                    }
+                                }) }
+                            </ul>
+                        }
                     </div>
                 </div>
                 <div ref={self.scroll_container_ref.clone()} class="scroll-container" onscroll={on_scroll}>
@@ -292,9 +475,9 @@ impl Component for HexEditor {
 // All helper methods are now correctly placed here.
 impl HexEditor {
     fn jump_to_match(&mut self, result_index: usize) {
-        if let Some(&match_start_byte) = self.search_results.get(result_index) {
+        if let Some(result) = self.search_results.get(result_index) {
             self.current_match_index = Some(result_index);
-            let target_row = (match_start_byte / BYTES_PER_ROW) as f64;
+            let target_row = (result.offset / BYTES_PER_ROW) as f64;
             let scroll_pos = target_row * ROW_HEIGHT - (self.container_height / 2.0);
             
             // This logic was missing from the previous attempt.
@@ -309,8 +492,18 @@ impl HexEditor {
         let offset = row_idx * BYTES_PER_ROW;
         let link = ctx.link();
         let current_match_range = self.current_match_index.and_then(|idx| {
-            self.search_results.get(idx).map(|&start| start..(start + self.search_bytes.len()))
+            self.search_results.get(idx).map(|r| r.offset..(r.offset + r.length))
         });
+        // search_results is sorted ascending by offset and every match shares
+        // the same length (they're all hits of one pattern), so `offset` and
+        // `offset + length` are both monotonic — binary search the slice that
+        // can possibly overlap this row once, instead of linear-scanning the
+        // whole match list for every byte cell.
+        let row_start = offset;
+        let row_end = offset + bytes.len();
+        let lower = self.search_results.partition_point(|r| r.offset + r.length <= row_start);
+        let upper = self.search_results.partition_point(|r| r.offset < row_end);
+        let row_matches = &self.search_results[lower..upper];
         html! {
             <tr>
                 <td class="text-secondary">{ format!("{:08X}", offset) }</td>
@@ -320,10 +513,14 @@ impl HexEditor {
                         let input: HtmlInputElement = e.target_unchecked_into();
                         Msg::UpdateByte(byte_idx, input.value())
                     });
-                    let is_highlighted = current_match_range.as_ref().map_or(false, |range| range.contains(&byte_idx));
+                    let is_current = current_match_range.as_ref().map_or(false, |range| range.contains(&byte_idx));
+                    let is_match = !is_current
+                        && row_matches.iter().any(|r| byte_idx >= r.offset && byte_idx < r.offset + r.length);
                     let mut class = "hex-input".to_string();
-                    if is_highlighted {
-                        class.push_str(" highlight");
+                    if is_current {
+                        class.push_str(" current-match");
+                    } else if is_match {
+                        class.push_str(" match");
                     }
                     html!{
                         <td class="text-center">
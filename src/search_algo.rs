@@ -0,0 +1,78 @@
+/// Finds every (possibly overlapping) occurrence of `needle` in `haystack`
+/// using Boyer-Moore-Horspool, invoking `on_match` with each ascending byte
+/// offset as soon as it's found rather than buffering them, so callers (e.g.
+/// the search worker) can stream results back instead of waiting for the
+/// whole scan to finish.
+pub fn find_all_with(haystack: &[u8], needle: &[u8], mut on_match: impl FnMut(usize)) {
+    let m = needle.len();
+    let n = haystack.len();
+    if m == 0 || m > n {
+        return;
+    }
+    if m == 1 {
+        for (i, &b) in haystack.iter().enumerate() {
+            if b == needle[0] {
+                on_match(i);
+            }
+        }
+        return;
+    }
+
+    let mut shift = [m; 256];
+    for (i, &b) in needle[..m - 1].iter().enumerate() {
+        shift[b as usize] = m - 1 - i;
+    }
+
+    let mut pos = 0;
+    while pos + m <= n {
+        if haystack[pos..pos + m] == *needle {
+            on_match(pos);
+            pos += 1;
+        } else {
+            pos += shift[haystack[pos + m - 1] as usize];
+        }
+    }
+}
+
+/// Finds every (possibly overlapping) occurrence of `needle` in `haystack`
+/// using Boyer-Moore-Horspool, returned as ascending byte offsets.
+///
+/// This replaces a naive `windows().filter_map()` scan, which is O(n*m) and
+/// dominates search time on large files; Horspool's bad-character shift lets
+/// most positions skip ahead by more than one byte.
+pub fn find_all(haystack: &[u8], needle: &[u8]) -> Vec<usize> {
+    let mut matches = Vec::new();
+    find_all_with(haystack, needle, |pos| matches.push(pos));
+    matches
+}
+
+#[cfg(test)]
+mod tests {
+    use super::find_all;
+
+    #[test]
+    fn finds_overlapping_matches() {
+        assert_eq!(find_all(b"aaaa", b"aa"), vec![0, 1, 2]);
+    }
+
+    #[test]
+    fn finds_match_at_end_of_buffer() {
+        assert_eq!(find_all(b"xxxabc", b"abc"), vec![3]);
+    }
+
+    #[test]
+    fn handles_empty_and_oversized_needle() {
+        assert_eq!(find_all(b"abc", b""), Vec::<usize>::new());
+        assert_eq!(find_all(b"ab", b"abcd"), Vec::<usize>::new());
+    }
+
+    #[test]
+    fn handles_single_byte_needle() {
+        assert_eq!(find_all(b"banana", b"a"), vec![1, 3, 5]);
+    }
+
+    #[test]
+    fn no_match_returns_empty() {
+        assert_eq!(find_all(b"hello world", b"xyz"), Vec::<usize>::new());
+    }
+}
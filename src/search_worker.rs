@@ -0,0 +1,139 @@
+use gloo_worker::{HandlerId, Worker, WorkerScope};
+use serde::{Deserialize, Serialize};
+
+use crate::search_algo::find_all_with;
+
+/// A single match: the offset it starts at and how many bytes it covers.
+#[derive(Clone, Copy, Serialize, Deserialize)]
+pub struct SearchResult {
+    pub offset: usize,
+    pub length: usize,
+}
+
+/// How many matches to batch up before posting a partial result back to the
+/// component, so the UI gets incremental updates on large files.
+const CHUNK_SIZE: usize = 256;
+
+/// Request sent from the `HexEditor` component to the search worker.
+///
+/// `pattern` is a sequence of `(mask, value)` pairs: a plain byte is
+/// `(0xFF, b)`, while hex wildcards (`??`, `D?`, `?F`) relax the mask so a
+/// haystack byte `h` matches when `h & mask == value`.
+#[derive(Clone, Serialize, Deserialize)]
+pub struct SearchRequest {
+    /// Echoed back on every response so the component can tell a superseded
+    /// search's results apart from the latest one (the worker is long-lived
+    /// and reused across searches, so dropping a stale request isn't enough).
+    pub generation: u64,
+    pub haystack: Vec<u8>,
+    pub pattern: Vec<(u8, u8)>,
+    /// Byte window to scan, as `[range_start, range_end)`.
+    pub range_start: usize,
+    pub range_end: usize,
+    /// When set, matches are reported *outside* `[range_start, range_end)` instead of inside it.
+    pub invert_range: bool,
+}
+
+/// Streamed responses from the search worker: zero or more `Partial` chunks
+/// as matches are found, followed by exactly one `Done`. Both carry the
+/// `generation` of the request they answer.
+#[derive(Clone, Serialize, Deserialize)]
+pub enum SearchResponse {
+    Partial(u64, Vec<SearchResult>),
+    Done(u64),
+}
+
+/// Scans `haystack` for `pattern` (exact bytes via Boyer-Moore-Horspool, or a
+/// byte-by-byte mask/value test when it contains wildcards), invoking
+/// `on_match` with each match's start offset *within `haystack`* as it's found.
+fn scan(haystack: &[u8], pattern: &[(u8, u8)], mut on_match: impl FnMut(usize)) {
+    let len = pattern.len();
+    let is_exact = pattern.iter().all(|&(mask, _)| mask == 0xFF);
+    if is_exact {
+        let needle: Vec<u8> = pattern.iter().map(|&(_, value)| value).collect();
+        find_all_with(haystack, &needle, &mut on_match);
+    } else {
+        for (i, window) in haystack.windows(len).enumerate() {
+            if window
+                .iter()
+                .zip(pattern)
+                .all(|(b, (mask, value))| b & mask == *value)
+            {
+                on_match(i);
+            }
+        }
+    }
+}
+
+/// Runs the byte scan off the main thread so large files don't freeze the UI.
+pub struct SearchWorker;
+
+impl Worker for SearchWorker {
+    type Message = ();
+    type Input = SearchRequest;
+    type Output = SearchResponse;
+
+    fn create(_scope: &WorkerScope<Self>) -> Self {
+        SearchWorker
+    }
+
+    fn update(&mut self, _scope: &WorkerScope<Self>, _msg: Self::Message) {}
+
+    fn received(&mut self, scope: &WorkerScope<Self>, msg: Self::Input, id: HandlerId) {
+        let len = msg.pattern.len();
+        if len == 0 || len > msg.haystack.len() {
+            scope.respond(id, SearchResponse::Done(msg.generation));
+            return;
+        }
+
+        let mut batch = Vec::with_capacity(CHUNK_SIZE);
+        {
+            // Emits (and, once a chunk fills up, posts) matches as they're found
+            // instead of collecting every offset before responding, so the
+            // component sees results stream in on large files rather than
+            // getting them all in one burst after the whole scan completes.
+            let mut emit = |offset: usize| {
+                batch.push(SearchResult { offset, length: len });
+                if batch.len() >= CHUNK_SIZE {
+                    scope.respond(
+                        id,
+                        SearchResponse::Partial(msg.generation, std::mem::take(&mut batch)),
+                    );
+                }
+            };
+
+            if msg.invert_range {
+                // Matches outside the excluded region can land on either side of
+                // it, so there's no single contiguous slice to scan: scan
+                // everything and filter by offset instead.
+                scan(&msg.haystack, &msg.pattern, |offset| {
+                    if offset < msg.range_start || offset >= msg.range_end {
+                        emit(offset);
+                    }
+                });
+            } else {
+                // Only windows starting inside [range_start, range_end) can
+                // match, so scanning just that slice (padded so a match
+                // straddling the window's tail isn't missed) keeps a narrow
+                // re-scan of a huge file cheap instead of always walking the
+                // whole buffer.
+                let scan_start = msg.range_start.min(msg.haystack.len());
+                let scan_end = msg
+                    .range_end
+                    .saturating_add(len - 1)
+                    .min(msg.haystack.len())
+                    .max(scan_start);
+                let window_len = msg.range_end.saturating_sub(scan_start);
+                scan(&msg.haystack[scan_start..scan_end], &msg.pattern, |offset| {
+                    if offset < window_len {
+                        emit(scan_start + offset);
+                    }
+                });
+            }
+        }
+        if !batch.is_empty() {
+            scope.respond(id, SearchResponse::Partial(msg.generation, batch));
+        }
+        scope.respond(id, SearchResponse::Done(msg.generation));
+    }
+}